@@ -1,36 +1,374 @@
 use std::env;
 use std::fs::File;
-use std::io::{self, Read, Write, BufReader};
-
-/// Copy data from the reader to the writer using a small buffer.
-/// When `unbuffered` is true, data is flushed immediately after each read.
-fn copy_reader<R: Read, W: Write>(reader: &mut R, writer: &mut W, unbuffered: bool) -> io::Result<()> {
-    // Local buffer for transfers
-    let mut buf = [0u8; 512];
-    // Optional buffer for batched writes
-    let mut out_buf: Vec<u8> = Vec::new();
-    loop {
-        let n = reader.read(&mut buf)?;
-        if n == 0 {
-            break;
+use std::io::{self, BufReader, IsTerminal, Read, Write};
+
+/// Default transfer/flush buffer capacity, matching `std::io::BufWriter`'s default.
+const DEFAULT_BUF_SIZE: usize = 8192;
+
+/// Finds the first occurrence of `needle` in `haystack`.
+fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle)
+}
+
+/// Finds the last occurrence of `needle` in `haystack`.
+fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().rposition(|&b| b == needle)
+}
+
+/// Selects when `BufferedCopy` flushes buffered output, mirroring stdio's
+/// line-buffered-on-a-terminal / block-buffered-on-a-pipe behavior.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FlushPolicy {
+    /// Flush after every read, as if `-u` were given.
+    Unbuffered,
+    /// Flush up through the last newline seen so far, as stdout does on a tty.
+    LineBuffered,
+    /// Flush once the output buffer reaches capacity.
+    BlockBuffered,
+}
+
+/// Formatting options for GNU-style `cat` line transforms (`-n -b -s -E -T -v`).
+#[derive(Default, Clone, Copy)]
+struct LineOptions {
+    number_all: bool,
+    number_nonblank: bool,
+    squeeze_blank: bool,
+    show_ends: bool,
+    show_tabs: bool,
+    show_nonprinting: bool,
+}
+
+impl LineOptions {
+    /// Whether any line transform is requested; if not, the raw byte path is used.
+    fn active(&self) -> bool {
+        self.number_all
+            || self.number_nonblank
+            || self.squeeze_blank
+            || self.show_ends
+            || self.show_tabs
+            || self.show_nonprinting
+    }
+}
+
+/// Applies GNU `cat`-style line transforms to a byte stream as it arrives.
+///
+/// Lines are located with a `memchr` scan for `\n` in each filled read buffer; any
+/// trailing bytes that don't yet contain a newline are carried in `holdover` until
+/// the next buffer, or until `finish` is called at end of input, completes them.
+/// `line_number` and `prev_blank` persist across buffers (and across files, since
+/// one formatter is shared for the whole argument list) so `-n`/`-b`/`-s` behave
+/// correctly at chunk and file boundaries.
+struct LineFormatter {
+    options: LineOptions,
+    line_number: usize,
+    prev_blank: bool,
+    holdover: Vec<u8>,
+}
+
+impl LineFormatter {
+    fn new(options: LineOptions) -> Self {
+        Self {
+            options,
+            line_number: 0,
+            prev_blank: false,
+            holdover: Vec::new(),
+        }
+    }
+
+    /// Formats `chunk`, appending the result to `out`. Any trailing partial line is
+    /// retained in `holdover` rather than emitted.
+    fn format(&mut self, chunk: &[u8], out: &mut Vec<u8>) {
+        let mut data = chunk;
+        loop {
+            match memchr(b'\n', data) {
+                Some(pos) => {
+                    if self.holdover.is_empty() {
+                        self.format_line(&data[..pos], true, out);
+                    } else {
+                        self.holdover.extend_from_slice(&data[..pos]);
+                        let line = std::mem::take(&mut self.holdover);
+                        self.format_line(&line, true, out);
+                    }
+                    data = &data[pos + 1..];
+                }
+                None => {
+                    self.holdover.extend_from_slice(data);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Flushes an incomplete trailing line at end of input, if any.
+    fn finish(&mut self, out: &mut Vec<u8>) {
+        if !self.holdover.is_empty() {
+            let line = std::mem::take(&mut self.holdover);
+            self.format_line(&line, false, out);
         }
-        if unbuffered {
-            // Write immediately when running unbuffered
-            writer.write_all(&buf[..n])?;
+    }
+
+    /// Formats a single line's content (without its newline) and appends it to
+    /// `out`, handling blank-line squeezing, numbering, and byte transforms.
+    /// `terminated` indicates whether the line had a trailing `\n` in the input.
+    fn format_line(&mut self, content: &[u8], terminated: bool, out: &mut Vec<u8>) {
+        let blank = content.is_empty();
+
+        if self.options.squeeze_blank && blank && self.prev_blank {
+            // Drop repeated blank lines entirely; don't advance numbering.
+            return;
+        }
+        self.prev_blank = blank;
+
+        // -b takes precedence over -n, matching GNU cat.
+        let number = if self.options.number_nonblank {
+            !blank
         } else {
-            // Accumulate output and flush when full
-            out_buf.extend_from_slice(&buf[..n]);
-            if out_buf.len() >= 512 {
-                writer.write_all(&out_buf)?;
-                out_buf.clear();
+            self.options.number_all
+        };
+        if number {
+            self.line_number += 1;
+            out.extend_from_slice(format!("{:6}\t", self.line_number).as_bytes());
+        }
+
+        for &byte in content {
+            self.push_byte(byte, out);
+        }
+
+        if terminated {
+            if self.options.show_ends {
+                out.push(b'$');
             }
+            out.push(b'\n');
         }
     }
-    // Flush any remaining buffered data
-    if !unbuffered && !out_buf.is_empty() {
-        writer.write_all(&out_buf)?;
+
+    /// Renders a single content byte, applying `-T` tab rendering and `-v`
+    /// non-printing caret/`M-` notation. Tab and newline are never touched by `-v`.
+    fn push_byte(&self, byte: u8, out: &mut Vec<u8>) {
+        if byte == b'\t' {
+            if self.options.show_tabs {
+                out.extend_from_slice(b"^I");
+            } else {
+                out.push(byte);
+            }
+            return;
+        }
+        if !self.options.show_nonprinting {
+            out.push(byte);
+            return;
+        }
+        if byte >= 128 {
+            out.extend_from_slice(b"M-");
+            self.push_caret(byte - 128, out);
+        } else {
+            self.push_caret(byte, out);
+        }
+    }
+
+    /// Renders a low-7-bit byte in caret notation if it is a control character.
+    fn push_caret(&self, byte: u8, out: &mut Vec<u8>) {
+        if byte < 32 {
+            out.push(b'^');
+            out.push(byte + 64);
+        } else if byte == 127 {
+            out.extend_from_slice(b"^?");
+        } else {
+            out.push(byte);
+        }
+    }
+}
+
+/// Shared byte/line read budget enforced by `LimitReader`.
+///
+/// A single instance is created for the whole argument list and reused across
+/// every file, so `-c`/`-l` are honored as one global budget rather than being
+/// reset per file.
+struct CopyLimits {
+    bytes_remaining: Option<usize>,
+    lines_remaining: Option<usize>,
+}
+
+impl CopyLimits {
+    fn new(byte_limit: Option<usize>, line_limit: Option<usize>) -> Self {
+        Self {
+            bytes_remaining: byte_limit,
+            lines_remaining: line_limit,
+        }
+    }
+
+    fn exhausted(&self) -> bool {
+        self.bytes_remaining == Some(0) || self.lines_remaining == Some(0)
+    }
+}
+
+/// Wraps a reader so it stops once a shared [`CopyLimits`] budget is exhausted.
+///
+/// On each `read`, the caller's buffer is first clamped to `min(buf.len(), remaining)`
+/// bytes when a byte budget is active. If a line budget is also active, the bytes
+/// actually read are scanned with `memchr` for `\n`, and the result is clamped again
+/// to end just past the Nth newline, so `BufferedCopy::copy` terminates naturally once
+/// either budget reaches zero (by returning `Ok(0)`).
+struct LimitReader<'a, R> {
+    inner: R,
+    limits: &'a mut CopyLimits,
+}
+
+impl<'a, R: Read> LimitReader<'a, R> {
+    fn new(inner: R, limits: &'a mut CopyLimits) -> Self {
+        Self { inner, limits }
+    }
+}
+
+impl<'a, R: Read> Read for LimitReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.limits.exhausted() {
+            return Ok(0);
+        }
+
+        let cap = match self.limits.bytes_remaining {
+            Some(remaining) => buf.len().min(remaining),
+            None => buf.len(),
+        };
+        if cap == 0 {
+            return Ok(0);
+        }
+
+        let mut n = self.inner.read(&mut buf[..cap])?;
+        if n == 0 {
+            return Ok(0);
+        }
+
+        // Clamp to the line budget first, since it may truncate `n` further; the
+        // byte budget must only be charged for what's actually emitted below.
+        if let Some(remaining) = self.limits.lines_remaining {
+            let mut found = 0;
+            let mut scan_pos = 0;
+            let mut cutoff = None;
+            while scan_pos < n {
+                match memchr(b'\n', &buf[scan_pos..n]) {
+                    Some(idx) => {
+                        found += 1;
+                        scan_pos += idx + 1;
+                        if found == remaining {
+                            cutoff = Some(scan_pos);
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            match cutoff {
+                Some(cut) => {
+                    n = cut;
+                    self.limits.lines_remaining = Some(0);
+                }
+                None => self.limits.lines_remaining = Some(remaining - found),
+            }
+        }
+
+        if let Some(remaining) = self.limits.bytes_remaining.as_mut() {
+            *remaining -= n;
+        }
+
+        Ok(n)
+    }
+}
+
+/// Copies bytes from a reader to a writer using a configurable buffering policy.
+///
+/// Unlike a plain `BufWriter`, `BufferedCopy` lets the buffer capacity be chosen at
+/// runtime (via `-B`) and follows the [`FlushPolicy`] given to `new`; `main` picks
+/// that policy the way stdio does: line buffering on a terminal, block buffering
+/// otherwise, or unconditionally unbuffered when `-u` is given. This keeps the
+/// buffering policy testable independent of argument parsing, since it only
+/// depends on `new`'s parameters.
+struct BufferedCopy {
+    capacity: usize,
+    policy: FlushPolicy,
+}
+
+impl BufferedCopy {
+    /// Creates a new copier. `capacity` is the flush threshold under
+    /// `FlushPolicy::BlockBuffered` and the read chunk size in every mode.
+    fn new(capacity: usize, policy: FlushPolicy) -> Self {
+        Self { capacity, policy }
+    }
+
+    /// Copies all data from `reader` to `writer` according to `self.policy`. When
+    /// `formatter` is given, each buffer is run through it before being queued for
+    /// output; output still streams through the same buffering policy so large
+    /// files never materialize fully in memory.
+    fn copy<R: Read, W: Write>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        mut formatter: Option<&mut LineFormatter>,
+    ) -> io::Result<()> {
+        // Local buffer for transfers
+        let mut buf = vec![0u8; self.capacity.max(1)];
+        // Buffer for batched (or formatted) writes
+        let mut out_buf: Vec<u8> = Vec::new();
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            match formatter.as_mut() {
+                Some(f) => f.format(&buf[..n], &mut out_buf),
+                None => out_buf.extend_from_slice(&buf[..n]),
+            }
+            match self.policy {
+                FlushPolicy::Unbuffered => {
+                    writer.write_all(&out_buf)?;
+                    writer.flush()?;
+                    out_buf.clear();
+                }
+                FlushPolicy::LineBuffered => {
+                    // Flush through the last newline seen so far; retain any bytes
+                    // after it (an incomplete line) for the next buffer.
+                    if let Some(last_nl) = memrchr(b'\n', &out_buf) {
+                        writer.write_all(&out_buf[..=last_nl])?;
+                        writer.flush()?;
+                        out_buf.drain(..=last_nl);
+                    }
+                }
+                FlushPolicy::BlockBuffered => {
+                    if out_buf.len() >= self.capacity {
+                        writer.write_all(&out_buf)?;
+                        out_buf.clear();
+                    }
+                }
+            }
+        }
+        if let Some(f) = formatter.as_mut() {
+            f.finish(&mut out_buf);
+        }
+        if !out_buf.is_empty() {
+            writer.write_all(&out_buf)?;
+        }
+        Ok(())
+    }
+}
+
+/// Removes the flag at `args[i]` and its following numeric argument, returning the
+/// parsed value. Exits with a `cat`-style error message on a missing or invalid
+/// argument. Shared by every flag that takes a single numeric argument (`-B`,
+/// `-c`, `-l`); callers layer their own extra validation (e.g. `-B` rejecting 0)
+/// on top of the returned value.
+fn parse_limit_arg(args: &mut Vec<String>, i: usize, flag: char) -> usize {
+    args.remove(i);
+    if i >= args.len() {
+        eprintln!("cat: option requires an argument -- '{}'", flag);
+        std::process::exit(1);
+    }
+    let value = args.remove(i);
+    match value.parse::<usize>() {
+        Ok(n) => n,
+        Err(_) => {
+            eprintln!("cat: invalid number '{}' for -{}", value, flag);
+            std::process::exit(1);
+        }
     }
-    Ok(())
 }
 
 fn main() -> io::Result<()> {
@@ -38,29 +376,106 @@ fn main() -> io::Result<()> {
     let mut args: Vec<String> = env::args().skip(1).collect();
     // Check for -u which selects unbuffered mode
     let mut unbuffered = false;
-    if let Some(first) = args.first() {
-        if first == "-u" {
-            unbuffered = true;
-            args.remove(0);
+    // Transfer/flush buffer capacity, overridable with -B <bytes>
+    let mut buf_size = DEFAULT_BUF_SIZE;
+    let mut options = LineOptions::default();
+    // Global byte/line copy limits, overridable with -c <bytes> / -l <lines>
+    let mut byte_limit: Option<usize> = None;
+    let mut line_limit: Option<usize> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-u" => {
+                unbuffered = true;
+                args.remove(i);
+            }
+            "-c" => {
+                byte_limit = Some(parse_limit_arg(&mut args, i, 'c'));
+            }
+            "-l" => {
+                line_limit = Some(parse_limit_arg(&mut args, i, 'l'));
+            }
+            "-n" => {
+                options.number_all = true;
+                args.remove(i);
+            }
+            "-b" => {
+                options.number_nonblank = true;
+                args.remove(i);
+            }
+            "-s" => {
+                options.squeeze_blank = true;
+                args.remove(i);
+            }
+            "-E" => {
+                options.show_ends = true;
+                args.remove(i);
+            }
+            "-T" => {
+                options.show_tabs = true;
+                args.remove(i);
+            }
+            "-v" => {
+                options.show_nonprinting = true;
+                args.remove(i);
+            }
+            "-B" => {
+                buf_size = parse_limit_arg(&mut args, i, 'B');
+                if buf_size == 0 {
+                    eprintln!("cat: invalid buffer size '0': must be greater than 0");
+                    std::process::exit(1);
+                }
+            }
+            _ => i += 1,
         }
     }
 
+    // Mirror stdio's buffering heuristic: line-buffer on a terminal, block-buffer
+    // otherwise, unless -u forces unbuffered regardless of the isatty probe. -u
+    // also forces capacity 1 (ignoring -B), so each byte is flushed immediately
+    // (with line transforms, an incomplete line is held until its newline arrives).
+    let copier = if unbuffered {
+        BufferedCopy::new(1, FlushPolicy::Unbuffered)
+    } else if io::stdout().is_terminal() {
+        BufferedCopy::new(buf_size, FlushPolicy::LineBuffered)
+    } else {
+        BufferedCopy::new(buf_size, FlushPolicy::BlockBuffered)
+    };
+
+    // One formatter is shared across all arguments so numbering and blank-line
+    // squeezing carry over between files, not just within one.
+    let mut formatter = if options.active() {
+        Some(LineFormatter::new(options))
+    } else {
+        None
+    };
+
+    // One budget shared across every argument so -c/-l limits are global.
+    let mut limits = CopyLimits::new(byte_limit, line_limit);
+
     let mut stdout = io::stdout();
 
     // If no files were provided, read from stdin
     if args.is_empty() {
-        copy_reader(&mut io::stdin(), &mut stdout, unbuffered)?;
+        let mut reader = LimitReader::new(io::stdin(), &mut limits);
+        copier.copy(&mut reader, &mut stdout, formatter.as_mut())?;
     } else {
         for fname in args {
+            if limits.exhausted() {
+                break;
+            }
             if fname == "-" {
                 // '-' denotes standard input
-                copy_reader(&mut io::stdin(), &mut stdout, unbuffered)?;
+                let mut reader = LimitReader::new(io::stdin(), &mut limits);
+                copier.copy(&mut reader, &mut stdout, formatter.as_mut())?;
             } else {
                 match File::open(&fname) {
                     Ok(file) => {
-                        // Use a buffered reader for files
-                        let mut reader = BufReader::new(file);
-                        copy_reader(&mut reader, &mut stdout, unbuffered)?;
+                        // Use a capacity-matched buffered reader for files
+                        let inner = BufReader::with_capacity(buf_size, file);
+                        let mut reader = LimitReader::new(inner, &mut limits);
+                        copier.copy(&mut reader, &mut stdout, formatter.as_mut())?;
                     }
                     Err(e) => {
                         // Mirror the C implementation's error reporting
@@ -75,3 +490,232 @@ fn main() -> io::Result<()> {
     stdout.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `Write` sink that records each write's length and every `flush` call, so
+    /// tests can assert on flush cadence, not just the final bytes produced.
+    struct RecordingWriter {
+        data: Vec<u8>,
+        writes: Vec<usize>,
+        flushes: usize,
+    }
+
+    impl RecordingWriter {
+        fn new() -> Self {
+            Self {
+                data: Vec::new(),
+                writes: Vec::new(),
+                flushes: 0,
+            }
+        }
+    }
+
+    impl Write for RecordingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            self.writes.push(buf.len());
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn block_buffered_flushes_once_per_full_capacity() {
+        let copier = BufferedCopy::new(4, FlushPolicy::BlockBuffered);
+        let mut reader: &[u8] = b"abcdefgh";
+        let mut writer = RecordingWriter::new();
+        copier.copy(&mut reader, &mut writer, None).unwrap();
+
+        assert_eq!(writer.data, b"abcdefgh");
+        assert_eq!(writer.writes, vec![4, 4]);
+    }
+
+    #[test]
+    fn block_buffered_flushes_leftover_below_capacity_at_end() {
+        let copier = BufferedCopy::new(4, FlushPolicy::BlockBuffered);
+        let mut reader: &[u8] = b"abcdef";
+        let mut writer = RecordingWriter::new();
+        copier.copy(&mut reader, &mut writer, None).unwrap();
+
+        assert_eq!(writer.data, b"abcdef");
+        // First read fills capacity and flushes; the trailing 2 bytes only flush
+        // once the reader is exhausted.
+        assert_eq!(writer.writes, vec![4, 2]);
+    }
+
+    #[test]
+    fn unbuffered_flushes_every_read() {
+        let copier = BufferedCopy::new(4, FlushPolicy::Unbuffered);
+        let mut reader: &[u8] = b"abcdefgh";
+        let mut writer = RecordingWriter::new();
+        copier.copy(&mut reader, &mut writer, None).unwrap();
+
+        assert_eq!(writer.data, b"abcdefgh");
+        assert_eq!(writer.writes, vec![4, 4]);
+        assert_eq!(writer.flushes, writer.writes.len());
+    }
+
+    #[test]
+    fn line_buffered_flushes_through_last_newline_and_holds_back_remainder() {
+        let copier = BufferedCopy::new(64, FlushPolicy::LineBuffered);
+        let mut reader: &[u8] = b"ab\ncd";
+        let mut writer = RecordingWriter::new();
+        copier.copy(&mut reader, &mut writer, None).unwrap();
+
+        assert_eq!(writer.data, b"ab\ncd");
+        // "ab\n" flushes as soon as the newline is seen; "cd" has no newline yet
+        // and is only written once the reader is exhausted.
+        assert_eq!(writer.writes, vec![3, 2]);
+        assert_eq!(writer.flushes, 1);
+    }
+
+    #[test]
+    fn line_buffered_with_no_newline_writes_only_at_end_without_flushing() {
+        let copier = BufferedCopy::new(64, FlushPolicy::LineBuffered);
+        let mut reader: &[u8] = b"no newline here";
+        let mut writer = RecordingWriter::new();
+        copier.copy(&mut reader, &mut writer, None).unwrap();
+
+        assert_eq!(writer.data, b"no newline here");
+        assert_eq!(writer.flushes, 0);
+        assert_eq!(writer.writes, vec![writer.data.len()]);
+    }
+
+    #[test]
+    fn line_formatter_numbers_lines_across_buffer_boundaries() {
+        let options = LineOptions {
+            number_all: true,
+            ..LineOptions::default()
+        };
+        let mut formatter = LineFormatter::new(options);
+        let mut out = Vec::new();
+        formatter.format(b"ab\ncd", &mut out);
+        formatter.format(b"ef\n", &mut out);
+        formatter.finish(&mut out);
+
+        assert_eq!(out, b"     1\tab\n     2\tcdef\n");
+    }
+
+    #[test]
+    fn line_formatter_squeezes_blank_lines_split_across_a_buffer_boundary() {
+        let options = LineOptions {
+            squeeze_blank: true,
+            ..LineOptions::default()
+        };
+        let mut formatter = LineFormatter::new(options);
+        let mut out = Vec::new();
+        // The second blank line's terminating "\n" arrives in the next buffer, but
+        // `prev_blank` must still carry across so it's squeezed away.
+        formatter.format(b"a\n\n", &mut out);
+        formatter.format(b"\nb\n", &mut out);
+        formatter.finish(&mut out);
+
+        assert_eq!(out, b"a\n\nb\n");
+    }
+
+    #[test]
+    fn line_formatter_number_nonblank_overrides_number_all_for_blank_lines() {
+        let options = LineOptions {
+            number_all: true,
+            number_nonblank: true,
+            ..LineOptions::default()
+        };
+        let mut formatter = LineFormatter::new(options);
+        let mut out = Vec::new();
+        formatter.format(b"a\n\nb\n", &mut out);
+        formatter.finish(&mut out);
+
+        assert_eq!(out, b"     1\ta\n\n     2\tb\n");
+    }
+
+    #[test]
+    fn line_formatter_renders_control_del_and_high_bit_bytes_under_dash_v() {
+        let options = LineOptions {
+            show_nonprinting: true,
+            ..LineOptions::default()
+        };
+        let mut formatter = LineFormatter::new(options);
+        let mut out = Vec::new();
+        formatter.format(&[0u8, 127, 200, b'A', b'\n'], &mut out);
+        formatter.finish(&mut out);
+
+        assert_eq!(out, b"^@^?M-HA\n");
+    }
+
+    #[test]
+    fn limit_reader_clamps_byte_budget_within_a_single_read() {
+        let mut limits = CopyLimits::new(Some(3), None);
+        let data: &[u8] = b"abcdef";
+        let mut reader = LimitReader::new(data, &mut limits);
+        let mut buf = [0u8; 16];
+
+        let n = reader.read(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"abc");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn limit_reader_cuts_mid_buffer_at_the_nth_newline() {
+        let mut limits = CopyLimits::new(None, Some(2));
+        let data: &[u8] = b"a\nb\nc\nd\n";
+        let mut reader = LimitReader::new(data, &mut limits);
+        let mut buf = [0u8; 16];
+
+        let n = reader.read(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"a\nb\n");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn limit_reader_byte_budget_wins_when_it_exhausts_before_the_line_budget() {
+        let mut limits = CopyLimits::new(Some(3), Some(5));
+        let data: &[u8] = b"ab\ncd\n";
+        let mut reader = LimitReader::new(data, &mut limits);
+        let mut buf = [0u8; 16];
+
+        let n = reader.read(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"ab\n");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn limit_reader_line_budget_wins_and_charges_only_the_truncated_bytes() {
+        let mut limits = CopyLimits::new(Some(100), Some(1));
+        let data: &[u8] = b"ab\ncd\n";
+        let mut reader = LimitReader::new(data, &mut limits);
+        let mut buf = [0u8; 16];
+
+        let n = reader.read(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"ab\n");
+        // Only the 3 emitted bytes should be charged against the byte budget, not
+        // the 6 bytes actually read from the inner reader.
+        assert_eq!(limits.bytes_remaining, Some(97));
+    }
+
+    #[test]
+    fn copy_limits_budget_carries_across_multiple_readers() {
+        let mut limits = CopyLimits::new(Some(5), None);
+        {
+            let data: &[u8] = b"abc";
+            let mut reader = LimitReader::new(data, &mut limits);
+            let mut buf = [0u8; 16];
+            let n = reader.read(&mut buf).unwrap();
+            assert_eq!(&buf[..n], b"abc");
+        }
+
+        // A second "file" reuses the same shared budget, so only 2 bytes remain
+        // regardless of how much the new reader has to offer.
+        let data2: &[u8] = b"wxyz";
+        let mut reader2 = LimitReader::new(data2, &mut limits);
+        let mut buf = [0u8; 16];
+        let n2 = reader2.read(&mut buf).unwrap();
+        assert_eq!(&buf[..n2], b"wx");
+        assert!(limits.exhausted());
+    }
+}